@@ -1,6 +1,18 @@
 #[cfg(doctest)]
 doc_comment::doctest!("../README.md");
 
+mod limited;
+mod multi;
+mod opts;
+mod replacer;
+mod sed;
+
+pub use limited::string_replace_all_n;
+pub use multi::string_replace_all_multi;
+pub use opts::{string_replace_all_opts, ReplaceOptions};
+pub use replacer::{replace_all_with, try_replace_all};
+pub use sed::{string_replace_sed, SedCommand, SedError};
+
 use regex::Regex;
 
 /// A trait that provides a `replace_all` method for `String` and `str` types,
@@ -43,6 +55,45 @@ pub trait StringReplaceAll {
     /// assert_eq!(result, "I think Ruth's ferret is cuter than your ferret!");
     /// ```
     fn replace_all<'a, P: Into<Pattern<'a>>>(&self, pattern: P, replacement: &str) -> String;
+
+    /// Replaces every occurrence of every `(pattern, replacement)` pair in a
+    /// single left-to-right pass, so an earlier replacement's output is never
+    /// re-matched by a later pattern.
+    ///
+    /// # See also
+    /// - [`string_replace_all_multi`] for matching and tie-break semantics.
+    fn replace_all_multi(&self, pairs: &[(Pattern<'_>, &str)]) -> String;
+
+    /// Replaces all occurrences of `pattern`, computing each replacement
+    /// with a closure instead of a fixed string.
+    ///
+    /// # See also
+    /// - [`replace_all_with`] for details on arguments and behavior.
+    fn replace_all_with<'a, P: Into<Pattern<'a>>, F: FnMut(&regex::Captures) -> String>(
+        &self,
+        pattern: P,
+        replacer: F,
+    ) -> String;
+
+    /// Replaces all occurrences of `pattern`, computing each replacement
+    /// with a fallible closure, short-circuiting on the first `Err`.
+    ///
+    /// # See also
+    /// - [`try_replace_all`] for details on arguments and behavior.
+    fn try_replace_all<'a, P: Into<Pattern<'a>>, F: FnMut(&regex::Captures) -> Result<String, E>, E>(
+        &self,
+        pattern: P,
+        replacer: F,
+    ) -> Result<String, E>;
+
+    /// Replaces only the first `n` occurrences of `pattern` (`n == 0` means
+    /// "replace all"). Like [`StringReplaceAll::replace_all`] and unlike the
+    /// free [`string_replace_all`], this never collapses consecutive
+    /// duplicates of the replacement.
+    ///
+    /// # See also
+    /// - [`string_replace_all_n`] for details on arguments and behavior.
+    fn replace_all_n<'a, P: Into<Pattern<'a>>>(&self, pattern: P, replacement: &str, n: usize) -> String;
 }
 
 /// Implementation of `StringReplaceAll` for `String`.
@@ -59,6 +110,35 @@ impl StringReplaceAll for String {
             Pattern::Regex(r) => r.replace_all(self, replacement).to_string(),
         }
     }
+
+    /// Replaces every occurrence of every `(pattern, replacement)` pair in a
+    /// single left-to-right pass.
+    ///
+    /// # See also
+    /// - [`string_replace_all_multi`] for details on arguments and behavior.
+    fn replace_all_multi(&self, pairs: &[(Pattern<'_>, &str)]) -> String {
+        string_replace_all_multi(self, pairs)
+    }
+
+    fn replace_all_with<'a, P: Into<Pattern<'a>>, F: FnMut(&regex::Captures) -> String>(
+        &self,
+        pattern: P,
+        replacer: F,
+    ) -> String {
+        replace_all_with(self, pattern, replacer)
+    }
+
+    fn try_replace_all<'a, P: Into<Pattern<'a>>, F: FnMut(&regex::Captures) -> Result<String, E>, E>(
+        &self,
+        pattern: P,
+        replacer: F,
+    ) -> Result<String, E> {
+        try_replace_all(self, pattern, replacer)
+    }
+
+    fn replace_all_n<'a, P: Into<Pattern<'a>>>(&self, pattern: P, replacement: &str, n: usize) -> String {
+        string_replace_all_n(self, pattern, replacement, n)
+    }
 }
 
 /// Implementation of `StringReplaceAll` for string slices (`str`).
@@ -75,6 +155,35 @@ impl StringReplaceAll for str {
     fn replace_all<'a, P: Into<Pattern<'a>>>(&self, pattern: P, replacement: &str) -> String {
         self.to_string().replace_all(pattern, replacement)
     }
+
+    /// Replaces every occurrence of every `(pattern, replacement)` pair in a
+    /// single left-to-right pass.
+    ///
+    /// # See also
+    /// - [`string_replace_all_multi`] for details on arguments and behavior.
+    fn replace_all_multi(&self, pairs: &[(Pattern<'_>, &str)]) -> String {
+        self.to_string().replace_all_multi(pairs)
+    }
+
+    fn replace_all_with<'a, P: Into<Pattern<'a>>, F: FnMut(&regex::Captures) -> String>(
+        &self,
+        pattern: P,
+        replacer: F,
+    ) -> String {
+        self.to_string().replace_all_with(pattern, replacer)
+    }
+
+    fn try_replace_all<'a, P: Into<Pattern<'a>>, F: FnMut(&regex::Captures) -> Result<String, E>, E>(
+        &self,
+        pattern: P,
+        replacer: F,
+    ) -> Result<String, E> {
+        self.to_string().try_replace_all(pattern, replacer)
+    }
+
+    fn replace_all_n<'a, P: Into<Pattern<'a>>>(&self, pattern: P, replacement: &str, n: usize) -> String {
+        self.to_string().replace_all_n(pattern, replacement, n)
+    }
 }
 
 /// Replaces all occurrences of `from` with `to` in `input`, supporting both exact string and regex replacements.
@@ -133,24 +242,25 @@ pub fn string_replace_all<'a, P: Into<Pattern<'a>>>(
     pattern: P,
     replacement: &str,
 ) -> String {
-    let mut result = match pattern.into() {
-        Pattern::Str(s) => {
-            if s == replacement || s.is_empty() {
-                return input.to_string();
-            }
-            input.replace(s, replacement)
-        }
-        Pattern::Regex(r) => r.replace_all(input, replacement).to_string(),
-    };
-
-    if !replacement.is_empty() {
-        let cleanup_pattern = Regex::new(&format!("(?:{})+", regex::escape(replacement))).unwrap();
-        result = cleanup_pattern
-            .replace_all(&result, replacement)
-            .to_string();
-    }
+    string_replace_all_opts(
+        input,
+        pattern,
+        replacement,
+        ReplaceOptions::new().collapse_consecutive(true),
+    )
+}
 
-    result
+/// Collapses runs of consecutive `replacement` occurrences in `input` down
+/// to one, e.g. turns `"  "` into `" "` after replacing `" "` with itself.
+///
+/// Shared by [`string_replace_all`] and [`string_replace_all_opts`] so the
+/// collapsing behavior stays identical between the two entry points.
+pub(crate) fn collapse_consecutive_duplicates(input: String, replacement: &str) -> String {
+    if replacement.is_empty() {
+        return input;
+    }
+    let cleanup_pattern = Regex::new(&format!("(?:{})+", regex::escape(replacement))).unwrap();
+    cleanup_pattern.replace_all(&input, replacement).to_string()
 }
 
 /// Allows both `&str` and `Regex` as input for `from`.
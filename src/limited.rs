@@ -0,0 +1,94 @@
+//! Count-limited replacement.
+//!
+//! [`string_replace_all_n`] mirrors `Regex::replacen` (and the common need
+//! to change just the first match) for both pattern kinds, replacing only
+//! the first `n` occurrences. Unlike the free [`crate::string_replace_all`],
+//! it never collapses consecutive duplicates of the replacement — limited
+//! replacement callers generally want exact output.
+
+use crate::Pattern;
+
+/// Replaces the first `n` occurrences of `pattern` in `input` with
+/// `replacement`. A `n` of `0` means "replace all", matching
+/// `Regex::replacen`'s convention.
+///
+/// For a `Pattern::Str` this walks `match_indices` and stops after `n`
+/// substitutions, copying the remainder of `input` verbatim. For a
+/// `Pattern::Regex` this delegates to `Regex::replacen`.
+///
+/// # Examples
+/// ```
+/// use string_replace_all::string_replace_all_n;
+///
+/// let result = string_replace_all_n("a a a a", "a", "b", 2);
+/// assert_eq!(result, "b b a a");
+///
+/// let result = string_replace_all_n("a a a a", "a", "b", 0); // 0 == all
+/// assert_eq!(result, "b b b b");
+/// ```
+pub fn string_replace_all_n<'a, P: Into<Pattern<'a>>>(
+    input: &str,
+    pattern: P,
+    replacement: &str,
+    n: usize,
+) -> String {
+    match pattern.into() {
+        Pattern::Str(s) => {
+            if s.is_empty() {
+                return input.to_string();
+            }
+            let mut result = String::with_capacity(input.len());
+            let mut last_end = 0;
+            let matches = input.match_indices(s);
+            let matches: Box<dyn Iterator<Item = (usize, &str)>> = if n == 0 {
+                Box::new(matches)
+            } else {
+                Box::new(matches.take(n))
+            };
+            for (start, matched) in matches {
+                result.push_str(&input[last_end..start]);
+                result.push_str(replacement);
+                last_end = start + matched.len();
+            }
+            result.push_str(&input[last_end..]);
+            result
+        }
+        Pattern::Regex(r) => r.replacen(input, n, replacement).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::string_replace_all_n;
+
+    #[test]
+    fn test_replace_first_n_occurrences() {
+        let result = string_replace_all_n("a a a a", "a", "b", 2);
+        assert_eq!(result, "b b a a");
+    }
+
+    #[test]
+    fn test_zero_means_replace_all() {
+        let result = string_replace_all_n("a a a a", "a", "b", 0);
+        assert_eq!(result, "b b b b");
+    }
+
+    #[test]
+    fn test_n_larger_than_match_count() {
+        let result = string_replace_all_n("a a", "a", "b", 10);
+        assert_eq!(result, "b b");
+    }
+
+    #[test]
+    fn test_does_not_collapse_consecutive_duplicates() {
+        let result = string_replace_all_n("a a a a", "a ", "b b", 0);
+        assert_eq!(result, "b bb bb ba");
+    }
+
+    #[test]
+    fn test_regex_pattern_delegates_to_replacen() {
+        let regex = regex::Regex::new(r"\d").unwrap();
+        let result = string_replace_all_n("1 2 3 4", &regex, "x", 2);
+        assert_eq!(result, "x x 3 4");
+    }
+}
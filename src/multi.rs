@@ -0,0 +1,329 @@
+//! Single-pass simultaneous multi-pattern replacement.
+//!
+//! [`string_replace_all_multi`] scans the input once, choosing at each
+//! position whichever pattern matches earliest (ties broken by the longest
+//! match, then by the order the pairs were given) so that an earlier
+//! replacement's output is never re-matched by a later pattern — unlike
+//! chaining [`crate::StringReplaceAll::replace_all`] calls.
+
+use crate::Pattern;
+use std::collections::{HashMap, VecDeque};
+
+/// A minimal Aho-Corasick automaton over a fixed set of literal byte strings.
+///
+/// Built once per call to [`string_replace_all_multi`] from the `Pattern::Str`
+/// entries, then walked a single time across the input.
+struct AhoCorasick {
+    children: Vec<HashMap<u8, usize>>,
+    fail: Vec<usize>,
+    /// Indices (into the original literal list) accepted at each node.
+    output: Vec<Vec<usize>>,
+    pattern_lens: Vec<usize>,
+}
+
+impl AhoCorasick {
+    fn new(patterns: &[&str]) -> Self {
+        let mut children = vec![HashMap::new()];
+        let mut output: Vec<Vec<usize>> = vec![Vec::new()];
+
+        for (idx, pattern) in patterns.iter().enumerate() {
+            let mut node = 0;
+            for &byte in pattern.as_bytes() {
+                node = match children[node].get(&byte) {
+                    Some(&child) => child,
+                    None => {
+                        children.push(HashMap::new());
+                        output.push(Vec::new());
+                        let child = children.len() - 1;
+                        children[node].insert(byte, child);
+                        child
+                    }
+                };
+            }
+            output[node].push(idx);
+        }
+
+        // BFS over the trie to wire up failure links, Aho-Corasick style.
+        let mut fail = vec![0usize; children.len()];
+        let mut queue = VecDeque::new();
+        for &child in children[0].values() {
+            queue.push_back(child);
+        }
+        while let Some(node) = queue.pop_front() {
+            let edges: Vec<(u8, usize)> = children[node].iter().map(|(&b, &c)| (b, c)).collect();
+            for (byte, child) in edges {
+                let mut f = fail[node];
+                while f != 0 && !children[f].contains_key(&byte) {
+                    f = fail[f];
+                }
+                let child_fail = children[f]
+                    .get(&byte)
+                    .copied()
+                    .filter(|&n| n != child)
+                    .unwrap_or(0);
+                fail[child] = child_fail;
+                let inherited = output[child_fail].clone();
+                output[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        AhoCorasick {
+            children,
+            fail,
+            output,
+            pattern_lens: patterns.iter().map(|p| p.len()).collect(),
+        }
+    }
+
+    /// Walks `haystack` exactly once, returning every occurrence of every
+    /// pattern as `(start, pattern_index)`, in the order the automaton's
+    /// state machine discovers them (by *end* position, not necessarily by
+    /// *start* position — a short pattern nested inside a longer one can be
+    /// found first even though the longer match starts earlier).
+    ///
+    /// This is the standard linear-time Aho-Corasick occurrence scan: no
+    /// position is revisited, so the whole call is O(haystack.len() + number
+    /// of occurrences) regardless of how many or how long the patterns are.
+    /// [`string_replace_all_multi`] sorts the result once to recover
+    /// earliest-start, longest-tie order.
+    fn find_all(&self, haystack: &[u8]) -> Vec<(usize, usize)> {
+        let mut occurrences = Vec::new();
+        let mut state = 0;
+        for (i, &byte) in haystack.iter().enumerate() {
+            while state != 0 && !self.children[state].contains_key(&byte) {
+                state = self.fail[state];
+            }
+            state = self.children[state].get(&byte).copied().unwrap_or(0);
+
+            for &idx in &self.output[state] {
+                occurrences.push((i + 1 - self.pattern_lens[idx], idx));
+            }
+        }
+        occurrences
+    }
+}
+
+/// One match candidate under consideration at the current scan position,
+/// regardless of whether it came from the automaton or a regex.
+struct Candidate<'p> {
+    start: usize,
+    end: usize,
+    replacement: &'p str,
+    order: usize,
+}
+
+/// Replaces every occurrence of every `(pattern, replacement)` pair in a
+/// single left-to-right pass over `input`.
+///
+/// At each position, the earliest-starting match across *all* patterns wins;
+/// ties are broken by the longest match, then by the order the pairs were
+/// given — matching Julia's `replace(str, pats...)` semantics. This means a
+/// replacement's own output is never re-scanned by a later pattern, which can
+/// happen when patterns are instead applied one at a time via repeated
+/// [`crate::StringReplaceAll::replace_all`] calls.
+///
+/// Literal (`Pattern::Str`) entries are matched with a single Aho-Corasick
+/// pass over the whole input, sorted once into earliest-start order;
+/// `Pattern::Regex` entries are matched by repeatedly asking the regex for
+/// its next match at or after the current position. Neither kind forces an
+/// O(n·k) rescan as the scan position advances.
+///
+/// # Examples
+/// ```
+/// use string_replace_all::{string_replace_all_multi, Pattern};
+///
+/// let pairs = [
+///     (Pattern::from("cat"), "dog"),
+///     (Pattern::from("dog"), "cat"),
+/// ];
+/// let result = string_replace_all_multi("cat and dog", &pairs);
+/// assert_eq!(result, "dog and cat"); // simultaneous, not a cascading swap
+/// ```
+pub fn string_replace_all_multi(input: &str, pairs: &[(Pattern<'_>, &str)]) -> String {
+    let mut literals: Vec<(&str, &str, usize)> = Vec::new();
+    let mut regexes: Vec<(&regex::Regex, &str, usize)> = Vec::new();
+
+    for (order, (pattern, replacement)) in pairs.iter().enumerate() {
+        match pattern {
+            Pattern::Str(s) if !s.is_empty() => literals.push((s, replacement, order)),
+            Pattern::Str(_) => {}
+            Pattern::Regex(r) => regexes.push((r, replacement, order)),
+        }
+    }
+
+    let bytes = input.as_bytes();
+
+    // Find every literal occurrence in one pass, then sort once into
+    // earliest-start order (ties: longest, then the order pairs were given)
+    // so the driving loop below can advance a simple pointer instead of
+    // re-scanning from `pos` on every step.
+    let mut literal_occurrences: Vec<(usize, usize)> = if literals.is_empty() {
+        Vec::new()
+    } else {
+        let needles: Vec<&str> = literals.iter().map(|&(s, _, _)| s).collect();
+        AhoCorasick::new(&needles).find_all(bytes)
+    };
+    literal_occurrences.sort_by(|&(a_start, a_idx), &(b_start, b_idx)| {
+        a_start
+            .cmp(&b_start)
+            .then_with(|| literals[b_idx].0.len().cmp(&literals[a_idx].0.len()))
+            .then_with(|| literals[a_idx].2.cmp(&literals[b_idx].2))
+    });
+    let mut lit_cursor = 0;
+
+    let mut result = String::with_capacity(input.len());
+    let mut pos = 0;
+    let mut regex_cursor: Vec<Option<(usize, usize)>> = vec![None; regexes.len()];
+
+    while pos < bytes.len() {
+        for (i, &(re, _, _)) in regexes.iter().enumerate() {
+            let stale = match regex_cursor[i] {
+                Some((start, _)) => start < pos,
+                None => true,
+            };
+            if stale {
+                regex_cursor[i] = re.find_at(input, pos).map(|m| (m.start(), m.end()));
+            }
+        }
+
+        while lit_cursor < literal_occurrences.len() && literal_occurrences[lit_cursor].0 < pos {
+            lit_cursor += 1;
+        }
+        let literal_candidate = literal_occurrences.get(lit_cursor).map(|&(start, idx)| {
+            let (needle, replacement, order) = literals[idx];
+            Candidate {
+                start,
+                end: start + needle.len(),
+                replacement,
+                order,
+            }
+        });
+
+        let regex_candidates =
+            regex_cursor
+                .iter()
+                .zip(regexes.iter())
+                .filter_map(|(cursor, &(_, replacement, order))| {
+                    cursor.map(|(start, end)| Candidate {
+                        start,
+                        end,
+                        replacement,
+                        order,
+                    })
+                });
+
+        let best = literal_candidate
+            .into_iter()
+            .chain(regex_candidates)
+            .min_by_key(|c| (c.start, std::cmp::Reverse(c.end - c.start), c.order));
+
+        match best {
+            Some(candidate) => {
+                result.push_str(&input[pos..candidate.start]);
+                result.push_str(candidate.replacement);
+                pos = if candidate.end > candidate.start {
+                    candidate.end
+                } else if candidate.start >= input.len() {
+                    // Zero-width match sitting at EOF (`$`, `\b`, an empty
+                    // regex): nothing left to copy, so just stop.
+                    candidate.start
+                } else {
+                    // Zero-width regex match: copy one char verbatim to guarantee progress.
+                    let next = input[candidate.start..]
+                        .chars()
+                        .next()
+                        .map_or(candidate.start + 1, |c| candidate.start + c.len_utf8());
+                    result.push_str(&input[candidate.start..next]);
+                    next
+                };
+            }
+            None => break,
+        }
+    }
+
+    result.push_str(&input[pos..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::string_replace_all_multi;
+    use crate::Pattern;
+    use std::time::Instant;
+
+    #[test]
+    fn test_simultaneous_swap_no_cascade() {
+        let pairs = [(Pattern::from("cat"), "dog"), (Pattern::from("dog"), "cat")];
+        let result = string_replace_all_multi("cat and dog", &pairs);
+        assert_eq!(result, "dog and cat");
+    }
+
+    #[test]
+    fn test_longest_match_wins_tie() {
+        let pairs = [(Pattern::from("a"), "1"), (Pattern::from("ab"), "2")];
+        let result = string_replace_all_multi("abc", &pairs);
+        assert_eq!(result, "2c");
+    }
+
+    #[test]
+    fn test_mixed_literal_and_regex() {
+        let regex = regex::Regex::new(r"\d+").unwrap();
+        let pairs = [
+            (Pattern::from("cat"), "dog"),
+            (Pattern::from(&regex), "NUM"),
+        ];
+        let result = string_replace_all_multi("cat 123 cat", &pairs);
+        assert_eq!(result, "dog NUM dog");
+    }
+
+    #[test]
+    fn test_zero_width_regex_candidate_at_eof_does_not_panic() {
+        let word_boundary = regex::Regex::new(r"\b").unwrap();
+        let pairs = [(Pattern::from(&word_boundary), "|")];
+        let result = string_replace_all_multi("ab", &pairs);
+        assert_eq!(result, "|ab|");
+
+        let end_anchored = regex::Regex::new(r"$").unwrap();
+        let pairs = [(Pattern::from(&end_anchored), "|")];
+        let result = string_replace_all_multi("ab", &pairs);
+        assert_eq!(result, "ab|");
+    }
+
+    #[test]
+    fn test_no_matches_returns_input_unchanged() {
+        let pairs = [(Pattern::from("xyz"), "abc")];
+        let result = string_replace_all_multi("nothing here", &pairs);
+        assert_eq!(result, "nothing here");
+    }
+
+    #[test]
+    fn test_empty_pairs_returns_input_unchanged() {
+        let pairs: [(Pattern<'_>, &str); 0] = [];
+        let result = string_replace_all_multi("unchanged", &pairs);
+        assert_eq!(result, "unchanged");
+    }
+
+    /// Regression test for an earlier tie-break fix that re-scanned up to
+    /// the longest pattern's length on every step, making the whole call
+    /// O(haystack.len() * longest_pattern.len()). A pattern that never
+    /// matches but is long enough to make that rescan expensive, run
+    /// against a haystack that matches a short pattern at every position,
+    /// should still finish in comfortably sub-second time.
+    #[test]
+    fn test_long_never_matching_pattern_does_not_blow_up_runtime() {
+        let long_needle = "z".to_string() + &"b".repeat(5000);
+        let input = "a".repeat(50_000);
+        let pairs = [
+            (Pattern::from("a"), "1"),
+            (Pattern::from(long_needle.as_str()), "2"),
+        ];
+
+        let start = Instant::now();
+        let result = string_replace_all_multi(&input, &pairs);
+        let elapsed = start.elapsed();
+
+        assert_eq!(result, "1".repeat(50_000));
+        assert!(elapsed.as_millis() < 500, "took {:?}", elapsed);
+    }
+}
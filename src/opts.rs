@@ -0,0 +1,262 @@
+//! Opt-in configuration for [`string_replace_all_opts`], separating the
+//! divergent behaviors the crate's entry points ship: the free
+//! [`crate::string_replace_all`] silently collapses consecutive duplicates of
+//! the replacement, while the [`crate::StringReplaceAll`] trait methods do
+//! not. [`ReplaceOptions`] lets callers choose explicitly instead of relying
+//! on which entry point they happened to call.
+
+use crate::{collapse_consecutive_duplicates, string_replace_all_n, Pattern};
+use regex::RegexBuilder;
+
+/// Options controlling [`string_replace_all_opts`]'s behavior.
+///
+/// Built with the builder methods below, then passed by value.
+///
+/// # Examples
+/// ```
+/// use string_replace_all::{string_replace_all_opts, ReplaceOptions};
+///
+/// let opts = ReplaceOptions::new().case_insensitive(true).limit(1);
+/// let result = string_replace_all_opts("Dog dog DOG", "dog", "cat", opts);
+/// assert_eq!(result, "cat dog DOG");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceOptions {
+    collapse_consecutive: bool,
+    case_insensitive: bool,
+    multi_line: bool,
+    limit: usize,
+    first_only: bool,
+    empty_pattern_inserts: bool,
+}
+
+impl ReplaceOptions {
+    /// Starts from the all-opt-out defaults: no collapsing, case-sensitive,
+    /// single-line, replace every match.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collapse runs of consecutive replacement occurrences down to one,
+    /// matching [`crate::string_replace_all`]'s historical behavior.
+    pub fn collapse_consecutive(mut self, yes: bool) -> Self {
+        self.collapse_consecutive = yes;
+        self
+    }
+
+    /// Match a literal `Pattern::Str` case-insensitively, without the caller
+    /// having to pre-build a `Regex`.
+    pub fn case_insensitive(mut self, yes: bool) -> Self {
+        self.case_insensitive = yes;
+        self
+    }
+
+    /// Set the regex multi-line flag on the regex built from a literal
+    /// `Pattern::Str`. The literal is escaped first, so a `^` or `$` in it
+    /// matches only that character and the flag does not change which text
+    /// matches. A `Pattern::Regex` is used as given.
+    pub fn multi_line(mut self, yes: bool) -> Self {
+        self.multi_line = yes;
+        self
+    }
+
+    /// Replace only the first `n` occurrences (`0` means "replace all"),
+    /// mirroring [`crate::string_replace_all_n`].
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = n;
+        self
+    }
+
+    /// Replace only the first occurrence. Equivalent to `limit(1)`, and
+    /// takes precedence over a separately set `limit`.
+    pub fn first_only(mut self, yes: bool) -> Self {
+        self.first_only = yes;
+        self
+    }
+
+    /// When `pattern` is an empty `Pattern::Str`, insert `replacement` at
+    /// every character boundary (before the first char, between each char,
+    /// and after the last) instead of leaving `input` unchanged.
+    ///
+    /// This mirrors JavaScript's `"".replaceAll("", x)`, which inserts `x` at
+    /// every boundary rather than treating an empty pattern as a no-op.
+    /// Disabled by default, since an empty pattern returning the input
+    /// unchanged is the crate's long-standing behavior.
+    pub fn empty_pattern_inserts(mut self, yes: bool) -> Self {
+        self.empty_pattern_inserts = yes;
+        self
+    }
+}
+
+/// Replaces occurrences of `pattern` in `input` according to `opts`.
+///
+/// This is the explicit, configurable counterpart to [`crate::string_replace_all`]:
+/// callers opt into consecutive-duplicate collapsing and regex flags instead
+/// of getting them implicitly (or not at all).
+///
+/// # Examples
+/// ```
+/// use string_replace_all::{string_replace_all_opts, ReplaceOptions};
+///
+/// // Collapsing is opt-in: without it, "    " -> "  " just replaces once.
+/// let result = string_replace_all_opts("a    b", "  ", " ", ReplaceOptions::new());
+/// assert_eq!(result, "a  b");
+///
+/// let result = string_replace_all_opts(
+///     "a    b",
+///     "  ",
+///     " ",
+///     ReplaceOptions::new().collapse_consecutive(true),
+/// );
+/// assert_eq!(result, "a b");
+///
+/// // An empty pattern is a no-op by default, but can insert at every
+/// // character boundary instead, like JavaScript's `replaceAll("", x)`.
+/// let result =
+///     string_replace_all_opts("ab", "", "-", ReplaceOptions::new().empty_pattern_inserts(true));
+/// assert_eq!(result, "-a-b-");
+/// ```
+pub fn string_replace_all_opts<'a, P: Into<Pattern<'a>>>(
+    input: &str,
+    pattern: P,
+    replacement: &str,
+    opts: ReplaceOptions,
+) -> String {
+    let effective_pattern = match pattern.into() {
+        Pattern::Str(s) if s.is_empty() && opts.empty_pattern_inserts => {
+            return insert_at_every_boundary(input, replacement);
+        }
+        Pattern::Str(s) if !s.is_empty() && (opts.case_insensitive || opts.multi_line) => {
+            let mut builder = RegexBuilder::new(&regex::escape(s));
+            builder.case_insensitive(opts.case_insensitive);
+            builder.multi_line(opts.multi_line);
+            Pattern::Regex(
+                builder
+                    .build()
+                    .expect("an escaped literal is always a valid regex"),
+            )
+        }
+        other => other,
+    };
+
+    let limit = if opts.first_only { 1 } else { opts.limit };
+    let result = string_replace_all_n(input, effective_pattern, replacement, limit);
+
+    if opts.collapse_consecutive {
+        collapse_consecutive_duplicates(result, replacement)
+    } else {
+        result
+    }
+}
+
+/// Inserts `replacement` at every character boundary of `input`: before the
+/// first char, between each pair of chars, and after the last — iterating
+/// over `char_indices` so insertions always land on valid UTF-8 boundaries.
+/// Produces exactly `input.chars().count() + 1` insertions; an empty `input`
+/// produces exactly one.
+fn insert_at_every_boundary(input: &str, replacement: &str) -> String {
+    if input.is_empty() {
+        return replacement.to_string();
+    }
+
+    let mut result =
+        String::with_capacity(input.len() + replacement.len() * (input.chars().count() + 1));
+    result.push_str(replacement);
+    for (_, ch) in input.char_indices() {
+        result.push(ch);
+        result.push_str(replacement);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{string_replace_all_opts, ReplaceOptions};
+
+    #[test]
+    fn test_collapse_consecutive_is_opt_in() {
+        let result = string_replace_all_opts("a    b", "  ", " ", ReplaceOptions::new());
+        assert_eq!(result, "a  b");
+    }
+
+    #[test]
+    fn test_collapse_consecutive_when_enabled() {
+        let result = string_replace_all_opts(
+            "a    b",
+            "  ",
+            " ",
+            ReplaceOptions::new().collapse_consecutive(true),
+        );
+        assert_eq!(result, "a b");
+    }
+
+    #[test]
+    fn test_case_insensitive_on_literal_pattern() {
+        let result = string_replace_all_opts(
+            "Dog dog DOG",
+            "dog",
+            "cat",
+            ReplaceOptions::new().case_insensitive(true),
+        );
+        assert_eq!(result, "cat cat cat");
+    }
+
+    #[test]
+    fn test_limit_restricts_to_first_n_matches() {
+        let result = string_replace_all_opts("a a a", "a", "b", ReplaceOptions::new().limit(1));
+        assert_eq!(result, "b a a");
+    }
+
+    #[test]
+    fn test_first_only_shorthand() {
+        let result =
+            string_replace_all_opts("a a a", "a", "b", ReplaceOptions::new().first_only(true));
+        assert_eq!(result, "b a a");
+    }
+
+    #[test]
+    fn test_empty_pattern_is_noop_by_default() {
+        let result = string_replace_all_opts("ab", "", "-", ReplaceOptions::new());
+        assert_eq!(result, "ab");
+    }
+
+    #[test]
+    fn test_empty_pattern_stays_noop_with_case_insensitive_or_multi_line() {
+        let result = string_replace_all_opts(
+            "ab",
+            "",
+            "-",
+            ReplaceOptions::new().case_insensitive(true),
+        );
+        assert_eq!(result, "ab");
+
+        let result =
+            string_replace_all_opts("ab", "", "-", ReplaceOptions::new().multi_line(true));
+        assert_eq!(result, "ab");
+    }
+
+    #[test]
+    fn test_empty_pattern_inserts_at_every_boundary() {
+        let result =
+            string_replace_all_opts("ab", "", "-", ReplaceOptions::new().empty_pattern_inserts(true));
+        assert_eq!(result, "-a-b-");
+    }
+
+    #[test]
+    fn test_empty_pattern_inserts_once_on_empty_input() {
+        let result =
+            string_replace_all_opts("", "", "-", ReplaceOptions::new().empty_pattern_inserts(true));
+        assert_eq!(result, "-");
+    }
+
+    #[test]
+    fn test_empty_pattern_inserts_respects_utf8_boundaries() {
+        let result = string_replace_all_opts(
+            "а́б",
+            "",
+            "|",
+            ReplaceOptions::new().empty_pattern_inserts(true),
+        );
+        assert_eq!(result.chars().filter(|&c| c == '|').count(), "а́б".chars().count() + 1);
+    }
+}
@@ -0,0 +1,156 @@
+//! Fallible and closure-based replacement.
+//!
+//! The `regex` crate's `Regex::replace_all` accepts a replacer closure but
+//! offers no way to propagate an error out of it. [`try_replace_all`] adds
+//! that: the first `Err` returned by the closure short-circuits the whole
+//! replacement, which matters when the replacement is computed by something
+//! fallible (file reads, parsing, lookups in a fallible map).
+
+use crate::Pattern;
+use regex::{Captures, Regex};
+
+/// Compiles a `Pattern` down to a `Regex`, synthesizing a single-group
+/// literal regex for `Pattern::Str` so both pattern kinds can be driven
+/// through the same `Captures`-based closure API.
+///
+/// Never called with an empty `Pattern::Str`: callers check that case first
+/// and return `input` unchanged, matching the no-op-by-default empty-pattern
+/// behavior established by [`crate::string_replace_all_n`] and
+/// [`crate::string_replace_all_opts`] (an empty regex would otherwise match
+/// at every character boundary).
+fn to_regex(pattern: Pattern<'_>) -> Regex {
+    match pattern {
+        Pattern::Str(s) => {
+            Regex::new(&regex::escape(s)).expect("an escaped literal is always a valid regex")
+        }
+        Pattern::Regex(r) => r,
+    }
+}
+
+/// Replaces all occurrences of `pattern` in `input`, computing each
+/// replacement with `replacer` rather than a fixed string.
+///
+/// # See also
+/// - [`try_replace_all`] for a variant that lets `replacer` fail.
+pub fn replace_all_with<'a, P, F>(input: &str, pattern: P, mut replacer: F) -> String
+where
+    P: Into<Pattern<'a>>,
+    F: FnMut(&Captures) -> String,
+{
+    let pattern = pattern.into();
+    if let Pattern::Str(s) = &pattern {
+        if s.is_empty() {
+            return input.to_string();
+        }
+    }
+    let regex = to_regex(pattern);
+    let mut result = String::with_capacity(input.len());
+    let mut last_end = 0;
+
+    for captures in regex.captures_iter(input) {
+        let whole = captures.get(0).expect("capture group 0 is always present");
+        result.push_str(&input[last_end..whole.start()]);
+        result.push_str(&replacer(&captures));
+        last_end = whole.end();
+    }
+    result.push_str(&input[last_end..]);
+    result
+}
+
+/// Replaces all occurrences of `pattern` in `input`, computing each
+/// replacement with `replacer`, which may fail.
+///
+/// Returns the first `Err` produced by `replacer`, short-circuiting before
+/// any later matches are visited.
+///
+/// # Examples
+/// ```
+/// use regex::Regex;
+/// use string_replace_all::try_replace_all;
+///
+/// let digits = Regex::new(r"\d+").unwrap();
+/// let result = try_replace_all("1 2 three", &digits, |caps| {
+///     caps[0].parse::<i32>().map(|n| (n * 2).to_string())
+/// });
+/// assert_eq!(result, Ok("2 4 three".to_string()));
+/// ```
+pub fn try_replace_all<'a, P, F, E>(input: &str, pattern: P, mut replacer: F) -> Result<String, E>
+where
+    P: Into<Pattern<'a>>,
+    F: FnMut(&Captures) -> Result<String, E>,
+{
+    let pattern = pattern.into();
+    if let Pattern::Str(s) = &pattern {
+        if s.is_empty() {
+            return Ok(input.to_string());
+        }
+    }
+    let regex = to_regex(pattern);
+    let mut result = String::with_capacity(input.len());
+    let mut last_end = 0;
+
+    for captures in regex.captures_iter(input) {
+        let whole = captures.get(0).expect("capture group 0 is always present");
+        result.push_str(&input[last_end..whole.start()]);
+        result.push_str(&replacer(&captures)?);
+        last_end = whole.end();
+    }
+    result.push_str(&input[last_end..]);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{replace_all_with, try_replace_all};
+
+    #[test]
+    fn test_replace_all_with_closure() {
+        let digits = regex::Regex::new(r"\d+").unwrap();
+        let result = replace_all_with("1 2 3", &digits, |caps| {
+            let n: i32 = caps[0].parse().unwrap();
+            (n * 10).to_string()
+        });
+        assert_eq!(result, "10 20 30");
+    }
+
+    #[test]
+    fn test_replace_all_with_literal_pattern() {
+        let result = replace_all_with("a-a-a", "a", |caps| caps[0].to_uppercase());
+        assert_eq!(result, "A-A-A");
+    }
+
+    #[test]
+    fn test_replace_all_with_empty_pattern_is_noop() {
+        let result = replace_all_with("ab", "", |caps| caps[0].to_uppercase());
+        assert_eq!(result, "ab");
+    }
+
+    #[test]
+    fn test_try_replace_all_success() {
+        let digits = regex::Regex::new(r"\d+").unwrap();
+        let result: Result<String, std::num::ParseIntError> = try_replace_all(
+            "1 2 3",
+            &digits,
+            |caps| caps[0].parse::<i32>().map(|n| (n * 2).to_string()),
+        );
+        assert_eq!(result, Ok("2 4 6".to_string()));
+    }
+
+    #[test]
+    fn test_try_replace_all_empty_pattern_is_noop() {
+        let result: Result<String, std::num::ParseIntError> =
+            try_replace_all("ab", "", |caps| caps[0].parse::<i32>().map(|n| n.to_string()));
+        assert_eq!(result, Ok("ab".to_string()));
+    }
+
+    #[test]
+    fn test_try_replace_all_short_circuits_on_error() {
+        let words = regex::Regex::new(r"\w+").unwrap();
+        let result: Result<String, std::num::ParseIntError> = try_replace_all(
+            "1 x 3",
+            &words,
+            |caps| caps[0].parse::<i32>().map(|n| n.to_string()),
+        );
+        assert!(result.is_err());
+    }
+}
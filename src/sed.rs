@@ -0,0 +1,204 @@
+//! Parsing and applying sed-style `s/pat/rep/flags` substitution commands.
+//!
+//! [`string_replace_sed`] accepts a familiar `s/regex/replacement/flags`
+//! command string (as typed in `sed` or Perl) and applies it to an input,
+//! so callers can drive the crate from config files or CLI arguments
+//! without hand-building [`regex::Regex`] objects.
+
+use crate::Pattern;
+use regex::RegexBuilder;
+use std::error::Error;
+use std::fmt;
+
+/// A parsed `s/pat/rep/flags` command, ready to apply to an input string.
+///
+/// Returned by [`Pattern::from_sed_command`]; most callers will instead reach
+/// for the [`string_replace_sed`] convenience function.
+pub struct SedCommand<'a> {
+    /// The compiled search pattern, always `Pattern::Regex` — flags like `i`,
+    /// `m` and `s` are baked in at parse time via [`RegexBuilder`].
+    pub pattern: Pattern<'a>,
+    /// The replacement text, as given (supports `regex` crate capture
+    /// references such as `$1` or `${name}`).
+    pub replacement: String,
+    /// Whether the `g` flag was present, i.e. replace every match rather
+    /// than just the first.
+    pub global: bool,
+}
+
+/// An error parsing or compiling a sed-style substitution command.
+#[derive(Debug)]
+pub enum SedError {
+    /// The command was missing its separators, had the wrong number of
+    /// parts, or used an unrecognized flag.
+    MalformedCommand(String),
+    /// The regex portion of the command failed to compile.
+    InvalidRegex(regex::Error),
+}
+
+impl fmt::Display for SedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SedError::MalformedCommand(message) => {
+                write!(f, "malformed sed command: {message}")
+            }
+            SedError::InvalidRegex(err) => write!(f, "invalid regex in sed command: {err}"),
+        }
+    }
+}
+
+impl Error for SedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SedError::InvalidRegex(err) => Some(err),
+            SedError::MalformedCommand(_) => None,
+        }
+    }
+}
+
+impl From<regex::Error> for SedError {
+    fn from(err: regex::Error) -> Self {
+        SedError::InvalidRegex(err)
+    }
+}
+
+impl<'a> Pattern<'a> {
+    /// Parses a sed-style `s/regex/replacement/flags` command into a
+    /// [`SedCommand`].
+    ///
+    /// The separator is taken from the second character of `command`, so
+    /// `s|a|b|g` works just as well as `s/a/b/g`. Recognized flags:
+    /// - `g` — replace all matches (the default without `g` is first-match-only).
+    /// - `i` — case insensitive.
+    /// - `m` — multi-line (`^`/`$` match at line boundaries).
+    /// - `s` — dot matches newline.
+    pub fn from_sed_command(command: &str) -> Result<SedCommand<'static>, SedError> {
+        let mut chars = command.char_indices();
+        let (_, leader) = chars
+            .next()
+            .ok_or_else(|| SedError::MalformedCommand("command is empty".to_string()))?;
+        if leader != 's' {
+            return Err(SedError::MalformedCommand(format!(
+                "command must start with 's', found '{leader}'"
+            )));
+        }
+        let (sep_index, separator) = chars.next().ok_or_else(|| {
+            SedError::MalformedCommand("command is missing a separator character".to_string())
+        })?;
+
+        let rest = &command[sep_index + separator.len_utf8()..];
+        let parts: Vec<&str> = rest.split(separator).collect();
+        if parts.len() != 3 {
+            return Err(SedError::MalformedCommand(format!(
+                "expected a regex, replacement and flags separated by '{separator}', found {} part(s)",
+                parts.len()
+            )));
+        }
+        let (pattern, replacement, flags) = (parts[0], parts[1], parts[2]);
+
+        let mut builder = RegexBuilder::new(pattern);
+        let mut global = false;
+        for flag in flags.chars() {
+            match flag {
+                'g' => global = true,
+                'i' => {
+                    builder.case_insensitive(true);
+                }
+                'm' => {
+                    builder.multi_line(true);
+                }
+                's' => {
+                    builder.dot_matches_new_line(true);
+                }
+                other => {
+                    return Err(SedError::MalformedCommand(format!(
+                        "unknown flag '{other}'"
+                    )))
+                }
+            }
+        }
+
+        Ok(SedCommand {
+            pattern: Pattern::Regex(builder.build()?),
+            replacement: replacement.to_string(),
+            global,
+        })
+    }
+}
+
+/// Parses `command` as a sed-style `s/regex/replacement/flags` substitution
+/// and applies it to `input`.
+///
+/// # Examples
+/// ```
+/// use string_replace_all::string_replace_sed;
+///
+/// let result = string_replace_sed("Hello World", "s/o/0/g").unwrap();
+/// assert_eq!(result, "Hell0 W0rld");
+///
+/// let result = string_replace_sed("Hello World", "s/o/0/").unwrap();
+/// assert_eq!(result, "Hell0 World"); // no `g`: first match only
+///
+/// let result = string_replace_sed("Hello World", "s|World|Rust|").unwrap();
+/// assert_eq!(result, "Hello Rust");
+/// ```
+pub fn string_replace_sed(input: &str, command: &str) -> Result<String, SedError> {
+    let sed = Pattern::from_sed_command(command)?;
+    let regex = match sed.pattern {
+        Pattern::Regex(regex) => regex,
+        Pattern::Str(_) => unreachable!("Pattern::from_sed_command always builds a Regex pattern"),
+    };
+
+    Ok(if sed.global {
+        regex.replace_all(input, sed.replacement.as_str()).to_string()
+    } else {
+        regex.replace(input, sed.replacement.as_str()).to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::string_replace_sed;
+
+    #[test]
+    fn test_global_flag_replaces_all() {
+        let result = string_replace_sed("Hello World", "s/o/0/g").unwrap();
+        assert_eq!(result, "Hell0 W0rld");
+    }
+
+    #[test]
+    fn test_without_global_flag_replaces_first_only() {
+        let result = string_replace_sed("Hello World", "s/o/0/").unwrap();
+        assert_eq!(result, "Hell0 World");
+    }
+
+    #[test]
+    fn test_alternate_separator() {
+        let result = string_replace_sed("path/to/file", "s|/|-|g").unwrap();
+        assert_eq!(result, "path-to-file");
+    }
+
+    #[test]
+    fn test_case_insensitive_flag() {
+        let result = string_replace_sed("Hello World", "s/hello/Hi/i").unwrap();
+        assert_eq!(result, "Hi World");
+    }
+
+    #[test]
+    fn test_malformed_command_missing_parts() {
+        let result = string_replace_sed("Hello World", "s/incomplete");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let result = string_replace_sed("Hello World", "s/[/x/g");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_unknown_flag() {
+        let result = string_replace_sed("Hello World", "s/o/0/z");
+        assert!(result.is_err());
+    }
+}